@@ -1,3 +1,11 @@
+use std::collections::VecDeque;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
 use helix_lsp::{lsp, ProgressStatus};
 use helix_view::graphics::Rect;
 use tui::{
@@ -11,34 +19,98 @@ use crate::compositor::{Component, EventResult};
 use super::Spinner;
 
 pub struct Fidget {
-    tx: std::sync::mpsc::Sender<FidgetMessage>,
+    tx: std::sync::mpsc::Sender<FidgetEvent>,
 }
 
 pub struct FidgetWidget {
     active: Vec<Provider>,
-    _spinner: Spinner,
-    // should_update_spinner: Arc<AtomicBool>,
-    // spinner_interval: std::time::Duration,
-    rx: std::sync::mpsc::Receiver<FidgetMessage>,
+    spinner: Spinner,
+    should_tick_spinner: Arc<AtomicBool>,
+    style: ProgressStyle,
+    rx: std::sync::mpsc::Receiver<FidgetEvent>,
+}
+
+/// The default template, read the same way as indicatif's own `{bar}`/`{percent}`/`{msg}` style:
+/// a `{token}` prefix, a progress bar, the plain percentage, the server-supplied title as
+/// `{prefix}`, the message, and an ETA when one can be estimated.
+const DEFAULT_TEMPLATE: &str = "[{token}] {bar} {percent} {prefix} - {msg} {eta}";
+
+/// Controls how an [`Item`] is rendered into its `line`.
+#[derive(Debug, Clone)]
+pub struct ProgressStyle {
+    /// Width, in columns, of the bar produced for the `{bar}` placeholder.
+    pub bar_width: usize,
+    /// A template string understood by [`render_template`], e.g. `"[{token}] {percent} {msg}"`.
+    /// Set through [`FidgetWidget::set_style`]; nothing in this module reads it from editor
+    /// config. Placeholders whose value is unavailable are dropped along with their bordering
+    /// separators so missing fields don't leave dangling fragments.
+    pub template: String,
+}
+
+impl Default for ProgressStyle {
+    fn default() -> Self {
+        Self {
+            bar_width: 20,
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
 }
 
 impl FidgetWidget {
     fn update(&mut self) {
-        if let Ok(msg) = self.rx.try_recv() {
-            if let Some(p) = self.active.iter_mut().find(|p| p.id == msg.id) {
-                p.update(&msg.token, msg.progress);
-            } else {
-                let mut p = Provider {
-                    id: msg.id,
-                    state: Vec::new(),
-                };
+        // Drain every event queued since the last render: `create` enqueues both a `Progress`
+        // and a `Rename` event for the same provider, and both must land before the next
+        // redraw or the provider would flash its fallback `server {id}` label for a frame.
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                FidgetEvent::Progress(msg) => {
+                    if let Some(p) = self.active.iter_mut().find(|p| p.id == msg.id) {
+                        p.update(&msg.token, msg.progress, &self.style);
+                    } else {
+                        let mut p = Provider {
+                            id: msg.id,
+                            name: None,
+                            collapsed: false,
+                            state: Vec::new(),
+                        };
 
-                p.update(&msg.token, msg.progress);
+                        p.update(&msg.token, msg.progress, &self.style);
 
-                self.active.push(p);
+                        self.active.push(p);
+                    }
+                }
+                FidgetEvent::Rename { id, name } => {
+                    if let Some(p) = self.active.iter_mut().find(|p| p.id == id) {
+                        p.name = Some(name);
+                    }
+                }
             }
         }
+
+        // Only keep nudging the compositor for redraws while there is something to animate.
+        // A finished provider lingers in `active` until dismissed with F3, so checking
+        // `is_empty()` alone would keep forcing redraws for a popup that's done animating.
+        self.should_tick_spinner.store(
+            self.active.iter().any(|p| !p.is_finished()),
+            Ordering::Relaxed,
+        );
     }
+
+    /// Applies editor-config-provided display settings (bar width, template string) to future
+    /// renders. There is no live config-reload path in this file, so callers re-apply this
+    /// whenever the relevant config changes.
+    ///
+    /// Reading `bar_width`/`template` out of the editor's config and calling this from the
+    /// compositor/app setup that owns [`fidget_and_widget`]'s return value is left to that
+    /// call site, which lives outside this module.
+    pub fn set_style(&mut self, style: ProgressStyle) {
+        self.style = style;
+    }
+}
+
+enum FidgetEvent {
+    Progress(FidgetMessage),
+    Rename { id: usize, name: String },
 }
 
 struct FidgetMessage {
@@ -47,28 +119,70 @@ struct FidgetMessage {
     pub progress: ProgressStatus,
 }
 
+/// Builds the `Fidget`/`FidgetWidget` pair with the built-in default [`ProgressStyle`]. Call
+/// [`FidgetWidget::set_style`] afterwards to apply settings read from editor config.
 pub fn fidget_and_widget() -> (Fidget, FidgetWidget) {
     let (tx, rx) = std::sync::mpsc::channel();
 
+    let spinner_interval = Duration::from_millis(200);
+    let should_tick_spinner = Arc::new(AtomicBool::new(false));
+
+    tokio::spawn(steady_tick(should_tick_spinner.clone(), spinner_interval));
+
     (
         Fidget { tx },
         FidgetWidget {
             active: Vec::new(),
-            _spinner: Spinner::dots(200),
-            // should_update_spinner: Arc::new(AtomicBool::new(true)),
-            // spinner_interval: Duration::from_millis(200),
+            spinner: Spinner::dots(spinner_interval.as_millis() as u64),
+            should_tick_spinner,
+            style: ProgressStyle::default(),
             rx,
         },
     )
 }
 
+/// Forces a redraw every `interval`, the way indicatif's steady-tick thread keeps a spinner
+/// moving between its owner explicitly printing frames. Without this, `FidgetWidget::render`
+/// would only ever be called in response to a new `FidgetMessage`, so the spinner would sit
+/// frozen between LSP progress reports instead of animating continuously.
+async fn steady_tick(should_tick: Arc<AtomicBool>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if should_tick.load(Ordering::Relaxed) {
+            helix_event::request_redraw();
+        }
+    }
+}
+
 struct Provider {
     id: usize,
+    name: Option<String>,
+    collapsed: bool,
     state: Vec<Item>,
 }
 
 impl Provider {
-    pub fn update(&mut self, token: &lsp::ProgressToken, progress: ProgressStatus) {
+    /// Human-readable header for this provider's tree node, falling back to its numeric id
+    /// when the language server hasn't been named yet.
+    fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("server {}", self.id),
+        }
+    }
+
+    /// A provider is dismissible once every item it is still tracking has finished.
+    fn is_finished(&self) -> bool {
+        !self.state.is_empty() && self.state.iter().all(|item| item.finished)
+    }
+
+    pub fn update(
+        &mut self,
+        token: &lsp::ProgressToken,
+        progress: ProgressStatus,
+        style: &ProgressStyle,
+    ) {
         self.state.retain(|item| item.finished == false);
 
         match progress {
@@ -78,11 +192,13 @@ impl Provider {
                     title: None,
                     line: None,
                     finished: false,
+                    started_at: None,
+                    samples: VecDeque::with_capacity(ETA_SAMPLE_WINDOW),
                 });
             }
             ProgressStatus::Started(progress) => {
                 if let Some(item) = self.state.iter_mut().find(|item| item.token == *token) {
-                    item.update(progress);
+                    item.update(progress, style);
                 } else {
                     log::warn!("progress token {:#?} was not registered", token);
                     return;
@@ -92,15 +208,23 @@ impl Provider {
     }
 }
 
+/// How many `(timestamp, percentage)` samples to keep for the rate estimate in [`Item::eta`].
+const ETA_SAMPLE_WINDOW: usize = 5;
+
 struct Item {
     token: lsp::ProgressToken,
     title: Option<String>,
     line: Option<String>,
     finished: bool,
+    started_at: Option<Instant>,
+    /// Recent `(timestamp, percentage)` samples, oldest first, used to estimate throughput.
+    samples: VecDeque<(Instant, u32)>,
 }
 
 impl Item {
-    fn update(&mut self, progress: lsp::WorkDoneProgress) {
+    fn update(&mut self, progress: lsp::WorkDoneProgress, style: &ProgressStyle) {
+        let started_at = *self.started_at.get_or_insert_with(Instant::now);
+
         let (msg, percentage) = match progress {
             lsp::WorkDoneProgress::Begin(lsp::WorkDoneProgressBegin {
                 title,
@@ -122,19 +246,84 @@ impl Item {
             }
         };
 
-        self.line = Some(format_progress(&self.token, &self.title, &msg, &percentage));
+        if let Some(percentage) = percentage {
+            if self.samples.len() == ETA_SAMPLE_WINDOW {
+                self.samples.pop_front();
+            }
+            self.samples.push_back((Instant::now(), percentage));
+        }
+
+        let fields = Fields {
+            token: token_display(&self.token),
+            prefix: self.title.clone(),
+            bar: percentage.map(|p| render_bar(p as f64 / 100.0, style.bar_width)),
+            percent: percentage.map(|p| format!("{}%", p)),
+            msg,
+            elapsed: Some(format_duration(started_at.elapsed())),
+            eta: percentage.and(self.eta()).map(format_eta),
+        };
+
+        self.line = Some(render_template(&style.template, &fields));
+    }
+
+    /// Estimates the time remaining from the recent rate of change of the percentage, the way
+    /// indicatif's download-speed example projects an ETA from a throughput sample window.
+    fn eta(&self) -> Option<Duration> {
+        estimate_eta(self.samples.front().copied(), self.samples.back().copied())
+    }
+}
+
+/// The pure rate projection behind [`Item::eta`], split out so it can be exercised with
+/// synthetic samples instead of real elapsed time.
+fn estimate_eta(first: Option<(Instant, u32)>, last: Option<(Instant, u32)>) -> Option<Duration> {
+    let (start_time, start_pct) = first?;
+    let (end_time, end_pct) = last?;
+
+    if end_pct <= start_pct {
+        return None;
+    }
+
+    let elapsed = end_time.saturating_duration_since(start_time).as_secs_f64();
+    if elapsed <= 0.0 {
+        return None;
+    }
+
+    let rate = (end_pct - start_pct) as f64 / elapsed;
+    if rate <= 0.0 {
+        return None;
+    }
+
+    let remaining = (100 - end_pct) as f64 / rate;
+    Some(Duration::from_secs_f64(remaining))
+}
+
+/// Formats a remaining-time estimate as e.g. `~12s left` or `~1m05s left`.
+fn format_eta(eta: Duration) -> String {
+    format!("~{} left", format_duration(eta))
+}
+
+/// Formats an elapsed/remaining duration as e.g. `12s` or `1m05s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
     }
 }
 
 impl Fidget {
-    pub fn create(&mut self, id: usize, token: lsp::ProgressToken) {
+    /// Registers a new progress token for server `id`, labelling its tree node with `name`
+    /// (e.g. `rust-analyzer`) instead of the raw numeric id.
+    pub fn create(&mut self, id: usize, name: impl Into<String>, token: lsp::ProgressToken) {
         self.tx
-            .send(FidgetMessage {
+            .send(FidgetEvent::Progress(FidgetMessage {
                 id,
                 token,
                 progress: ProgressStatus::Created,
-            })
-            .unwrap()
+            }))
+            .unwrap();
+        self.rename(id, name);
     }
 
     /// Ends the progress by removing the `token` from server with `id`, if removed returns the value.
@@ -145,38 +334,61 @@ impl Fidget {
         last_message: lsp::WorkDoneProgressEnd,
     ) {
         self.tx
-            .send(FidgetMessage {
+            .send(FidgetEvent::Progress(FidgetMessage {
                 id,
                 token,
                 progress: ProgressStatus::Started(lsp::WorkDoneProgress::End(last_message)),
-            })
+            }))
             .unwrap();
     }
 
     /// Updates the progess of `token` for server with `id` to `status`, returns the value replaced or `None`.
     pub fn update(&mut self, id: usize, token: lsp::ProgressToken, status: lsp::WorkDoneProgress) {
         self.tx
-            .send(FidgetMessage {
+            .send(FidgetEvent::Progress(FidgetMessage {
                 id,
                 token,
                 progress: ProgressStatus::Started(status),
+            }))
+            .unwrap();
+    }
+
+    /// Labels the tree node for server `id` with its resolved name (e.g. `rust-analyzer`)
+    /// instead of the raw numeric id.
+    pub fn rename(&mut self, id: usize, name: impl Into<String>) {
+        self.tx
+            .send(FidgetEvent::Rename {
+                id,
+                name: name.into(),
             })
             .unwrap();
     }
 }
 
 impl Component for FidgetWidget {
-    fn handle_event(
-        &mut self,
-        _event: crossterm::event::Event,
-        _ctx: &mut crate::compositor::Context,
-    ) -> EventResult {
-        // match event {
-        //     Event::Key(_) => EventResult::Ignored(Some(Box::new(|compositor, _| {
-        //         compositor.pop();
-        //     }))),
-        //     _ => EventResult::Ignored(None),
-        // }
+    fn handle_event(&mut self, event: Event, _ctx: &mut crate::compositor::Context) -> EventResult {
+        let Event::Key(KeyEvent { code, .. }) = event else {
+            return EventResult::Ignored(None);
+        };
+
+        // This popup stays alive for as long as any LSP progress is tracked (e.g. background
+        // indexing, which recurs on almost every edit), so binding `Tab`/`x` here would shadow
+        // core editing bindings (completion-accept, delete-char) for the whole session. Use
+        // function keys instead, and always `Ignored` so the keystroke still reaches the editor
+        // underneath, matching this widget's original passthrough contract.
+        match code {
+            // The topmost provider is the one rendered first, i.e. the last entry in `active`.
+            KeyCode::F(2) => {
+                if let Some(provider) = self.active.last_mut() {
+                    provider.collapsed = !provider.collapsed;
+                }
+            }
+            KeyCode::F(3) => {
+                self.active.retain(|provider| !provider.is_finished());
+            }
+            _ => {}
+        }
+
         EventResult::Ignored(None)
     }
 
@@ -189,11 +401,35 @@ impl Component for FidgetWidget {
         self.update();
         let mut to_render = Vec::new();
 
+        // The frame index advances by elapsed-time division rather than per-render, so the
+        // spinner's apparent speed doesn't depend on how often the compositor redraws us.
+        let frame_glyph = self.spinner.frame();
+
         for p in self.active.iter().rev() {
-            to_render.push(Spans::from(Span::raw(format!("id: {}", p.id))));
+            let marker = if p.collapsed { "▸" } else { "▾" };
+            to_render.push(Spans::from(Span::raw(format!("{} {}", marker, p.label()))));
+
+            if p.collapsed {
+                continue;
+            }
+
+            let mut items = p.state.iter().rev().peekable();
+            while let Some(item) = items.next() {
+                let connector = if items.peek().is_some() {
+                    "├─"
+                } else {
+                    "└─"
+                };
 
-            for item in p.state.iter().rev() {
                 if let Some(line) = &item.line {
+                    let line = if !item.finished {
+                        match frame_glyph {
+                            Some(glyph) => format!("{} {} {}", connector, glyph, line),
+                            None => format!("{} {}", connector, line),
+                        }
+                    } else {
+                        format!("{} {}", connector, line)
+                    };
                     to_render.push(Spans::from(Span::raw(line)))
                 }
             }
@@ -228,39 +464,253 @@ impl Component for FidgetWidget {
     }
 }
 
-fn format_progress(
-    token: &lsp::ProgressToken,
-    title: &Option<String>,
-    msg: &Option<String>,
-    percentage: &Option<u32>,
-) -> String {
-    let token: &dyn std::fmt::Display = match token {
-        lsp::NumberOrString::Number(n) => n,
-        lsp::NumberOrString::String(s) => s,
-    };
+/// The eighth-block glyphs used by [`render_bar`], from one eighth full to completely full.
+const BAR_PARTIALS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
 
-    match (title, msg, percentage) {
-        (Some(title), Some(message), Some(percentage)) => {
-            format!("[{}] {}% {} - {}", token, percentage, title, message)
-        }
-        (Some(title), None, Some(percentage)) => {
-            format!("[{}] {}% {}", token, percentage, title)
-        }
-        (Some(title), Some(message), None) => {
-            format!("[{}] {} - {}", token, title, message)
+/// Renders `fraction` (clamped to `0.0..=1.0`) as a `width`-column Unicode bar with sub-character
+/// resolution, the way indicatif's finebars do.
+fn render_bar(fraction: f64, width: usize) -> String {
+    let scaled = fraction.clamp(0.0, 1.0) * width as f64;
+    let full = (scaled.floor() as usize).min(width);
+
+    let mut bar = String::with_capacity(width);
+    bar.extend(std::iter::repeat('█').take(full));
+    let remainder = scaled - full as f64;
+    if full < width && remainder > 0.0 {
+        let partial = (remainder * 8.0).floor() as usize;
+        bar.push(BAR_PARTIALS[partial.min(7)]);
+    }
+    let rendered = bar.chars().count();
+    bar.extend(std::iter::repeat(' ').take(width.saturating_sub(rendered)));
+
+    bar
+}
+
+fn token_display(token: &lsp::ProgressToken) -> String {
+    match token {
+        lsp::NumberOrString::Number(n) => n.to_string(),
+        lsp::NumberOrString::String(s) => s.clone(),
+    }
+}
+
+/// The resolved value, if any, of every placeholder [`render_template`] understands for a
+/// single [`Item`].
+struct Fields {
+    token: String,
+    prefix: Option<String>,
+    bar: Option<String>,
+    percent: Option<String>,
+    msg: Option<String>,
+    elapsed: Option<String>,
+    eta: Option<String>,
+}
+
+impl Fields {
+    fn get(&self, placeholder: &str) -> Option<&str> {
+        match placeholder {
+            "token" => Some(&self.token),
+            "prefix" => self.prefix.as_deref(),
+            "bar" => self.bar.as_deref(),
+            "percent" => self.percent.as_deref(),
+            "msg" => self.msg.as_deref(),
+            "elapsed" => self.elapsed.as_deref(),
+            "eta" => self.eta.as_deref(),
+            _ => None,
         }
-        (None, Some(message), Some(percentage)) => {
-            format!("[{}] {}% {}", token, percentage, message)
+    }
+}
+
+#[derive(Clone)]
+enum TemplatePart {
+    Literal(String),
+    Placeholder(String),
+}
+
+/// Splits a template like `"[{token}] {percent} {msg}"` into literal and `{placeholder}` parts.
+/// An unclosed `{` is kept verbatim as a literal rather than treated as a placeholder.
+fn parse_template(template: &str) -> Vec<TemplatePart> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(TemplatePart::Literal(rest[..start].to_string()));
         }
-        (Some(title), None, None) => {
-            format!("[{}] {}", token, title)
+        rest = &rest[start + 1..];
+
+        match rest.find('}') {
+            Some(end) => {
+                parts.push(TemplatePart::Placeholder(rest[..end].to_string()));
+                rest = &rest[end + 1..];
+            }
+            None => {
+                parts.push(TemplatePart::Literal(format!("{{{}", rest)));
+                rest = "";
+            }
         }
-        (None, Some(message), None) => {
-            format!("[{}] {}", token, message)
+    }
+
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest.to_string()));
+    }
+
+    parts
+}
+
+/// Resolves every placeholder in `template` against `fields`, replacing the eight-arm
+/// combinatorial match `format_progress` used to be with a single pass. A placeholder whose
+/// value is unavailable is dropped, and so are the *bare* separators bordering it (whitespace
+/// and/or `-` only, e.g. `" "` or `" - "`), so a missing field doesn't leave a dangling fragment.
+/// When the output before the next written value doesn't already end in whitespace, the
+/// shortest of the dropped separators is put back, so the fields around the gap stay apart.
+///
+/// Only whitespace/`-`-only literals are eligible: a literal like `"] "` also closes a bracket
+/// opened around an earlier, still-present placeholder, so it must never be dropped just
+/// because the placeholder on its *other* side happens to be missing.
+fn render_template(template: &str, fields: &Fields) -> String {
+    let parts = parse_template(template);
+    let resolved: Vec<Option<&str>> = parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Literal(text) => Some(text.as_str()),
+            TemplatePart::Placeholder(name) => fields.get(name),
+        })
+        .collect();
+
+    let is_missing_placeholder = |i: usize| {
+        matches!(parts.get(i), Some(TemplatePart::Placeholder(_))) && resolved[i].is_none()
+    };
+
+    let mut out = String::new();
+    // The shortest separator dropped since the last value was written.
+    let mut dropped: Option<&str> = None;
+    for (i, value) in resolved.iter().enumerate() {
+        let Some(value) = value else { continue };
+
+        if matches!(parts[i], TemplatePart::Literal(_)) {
+            let is_bare_separator =
+                !value.is_empty() && value.chars().all(|c| c.is_whitespace() || c == '-');
+            let borders_missing =
+                (i > 0 && is_missing_placeholder(i - 1)) || is_missing_placeholder(i + 1);
+            if is_bare_separator && borders_missing {
+                if dropped.map_or(true, |d| value.len() < d.len()) {
+                    dropped = Some(*value);
+                }
+                continue;
+            }
         }
-        (None, None, Some(percentage)) => {
-            format!("[{}] {}%", token, percentage)
+
+        if let Some(sep) = dropped.take() {
+            if !out.is_empty() && !out.ends_with(char::is_whitespace) {
+                out.push_str(sep);
+            }
         }
-        (None, None, None) => format!("[{}]", token),
+        out.push_str(value);
+    }
+
+    out.trim().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_bar_empty_and_full() {
+        assert_eq!(render_bar(0.0, 8), "        ");
+        assert_eq!(render_bar(1.0, 8), "████████");
+    }
+
+    #[test]
+    fn render_bar_partial_block() {
+        // 4.4/8 eighths: 4 full blocks plus a partial glyph for the remaining 0.4.
+        assert_eq!(render_bar(0.55, 8), "████▌   ");
+    }
+
+    #[test]
+    fn render_bar_exact_eighth_boundary_has_no_partial() {
+        // 4/8 exactly: no remainder, so no partial glyph should follow the full blocks.
+        assert_eq!(render_bar(0.5, 8), "████    ");
+    }
+
+    #[test]
+    fn render_template_fills_every_placeholder() {
+        let fields = Fields {
+            token: "1".to_string(),
+            prefix: Some("rust-analyzer".to_string()),
+            bar: Some("████    ".to_string()),
+            percent: Some("50%".to_string()),
+            msg: Some("indexing".to_string()),
+            elapsed: Some("3s".to_string()),
+            eta: Some("~3s left".to_string()),
+        };
+
+        assert_eq!(
+            render_template("[{token}] {bar} {percent} {prefix} - {msg} {eta}", &fields),
+            "[1] ████     50% rust-analyzer - indexing ~3s left"
+        );
+    }
+
+    #[test]
+    fn render_template_drops_missing_placeholders_without_dangling_separators() {
+        // Regression: a title+message-only update (no percentage at all, the common case for
+        // servers that never report progress %) used to eat the "] " that closes `{token}`
+        // because it happened to border the missing `{bar}` placeholder.
+        let fields = Fields {
+            token: "1".to_string(),
+            prefix: Some("rust-analyzer".to_string()),
+            bar: None,
+            percent: None,
+            msg: Some("indexing".to_string()),
+            elapsed: Some("3s".to_string()),
+            eta: None,
+        };
+
+        assert_eq!(
+            render_template("[{token}] {bar} {percent} {prefix} - {msg} {eta}", &fields),
+            "[1] rust-analyzer - indexing"
+        );
+    }
+
+    #[test]
+    fn render_template_all_placeholders_missing() {
+        let fields = Fields {
+            token: "1".to_string(),
+            prefix: None,
+            bar: None,
+            percent: None,
+            msg: None,
+            elapsed: None,
+            eta: None,
+        };
+
+        assert_eq!(
+            render_template("[{token}] {bar} {percent} {prefix} - {msg} {eta}", &fields),
+            "[1]"
+        );
+    }
+
+    #[test]
+    fn estimate_eta_projects_remaining_time_from_rate() {
+        let start = Instant::now();
+        let first = Some((start, 0));
+        let last = Some((start + Duration::from_secs(10), 50));
+
+        // 50% in 10s => 5%/s => 50% remaining takes another 10s.
+        assert_eq!(estimate_eta(first, last), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn estimate_eta_none_when_stalled_or_incomplete() {
+        let start = Instant::now();
+
+        // No movement in the sample window: not a meaningful rate.
+        let stalled = Some((start, 50));
+        let stalled_later = Some((start + Duration::from_secs(5), 50));
+        assert_eq!(estimate_eta(stalled, stalled_later), None);
+
+        // Fewer than two samples.
+        assert_eq!(estimate_eta(None, Some((start, 50))), None);
+        assert_eq!(estimate_eta(Some((start, 50)), None), None);
     }
 }